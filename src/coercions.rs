@@ -7,6 +7,8 @@ pub enum ContentType {
     JSON,
     YAML,
     XML,
+    TOML,
+    RON,
     Plain,
     URLEncoded,
 }
@@ -18,6 +20,8 @@ const XML_TEXT_MIME : &str = "text/xml";
 const XML_APP_MIME : &str = "application/xml";
 const YAML_TEXT_MIME : &str = "text/yaml";
 const YAML_APP_MIME : &str = "application/yaml";
+const TOML_MIME : &str = "application/toml";
+const RON_MIME : &str = "application/ron";
 
 impl ContentType {
     pub fn from_str(s: &str) -> Self {
@@ -25,6 +29,8 @@ impl ContentType {
             JSON_MIME => ContentType::JSON,
             YAML_TEXT_MIME | YAML_APP_MIME => ContentType::YAML,
             XML_TEXT_MIME | XML_APP_MIME => ContentType::XML,
+            TOML_MIME => ContentType::TOML,
+            RON_MIME => ContentType::RON,
             TEXT_MIME => ContentType::Plain,
             FORM_MIME => ContentType::URLEncoded,
             _ => ContentType::JSON,
@@ -36,19 +42,221 @@ impl ContentType {
             Self::JSON => String::from(JSON_MIME),
             Self::YAML => String::from(YAML_TEXT_MIME),
             Self::XML => String::from(XML_APP_MIME),
+            Self::TOML => String::from(TOML_MIME),
+            Self::RON => String::from(RON_MIME),
             Self::Plain => String::from(TEXT_MIME),
             Self::URLEncoded => String::from(FORM_MIME),
         }
     }
+
+    /// Every MIME spelling [`from_str`](Self::from_str) accepts for this type.
+    ///
+    /// `as_header_value` only emits one canonical spelling, but clients send
+    /// either alias for YAML and XML; negotiation matches against all of them.
+    fn mime_aliases(&self) -> &'static [&'static str] {
+        match self {
+            Self::JSON => &[JSON_MIME],
+            Self::YAML => &[YAML_TEXT_MIME, YAML_APP_MIME],
+            Self::XML => &[XML_TEXT_MIME, XML_APP_MIME],
+            Self::TOML => &[TOML_MIME],
+            Self::RON => &[RON_MIME],
+            Self::Plain => &[TEXT_MIME],
+            Self::URLEncoded => &[FORM_MIME],
+        }
+    }
+
+    /// The content types the FDK can negotiate, in descending server preference.
+    pub fn supported() -> [ContentType; 7] {
+        [
+            ContentType::JSON,
+            ContentType::YAML,
+            ContentType::XML,
+            ContentType::TOML,
+            ContentType::RON,
+            ContentType::Plain,
+            ContentType::URLEncoded,
+        ]
+    }
+
+    /// Select the response content type from a request `Accept` header.
+    ///
+    /// The header is parsed as comma-separated media ranges with optional `q=`
+    /// quality weights; `*/*` and `type/*` wildcards match accordingly. Each
+    /// supported type takes its quality from the *most specific* matching range
+    /// (exact `type/subtype` over `type/*` over `*/*`), per RFC 7231 §5.3.2, so
+    /// a broad `*/*` cannot override a narrower per-type weight. The supported
+    /// type with the highest acceptable quality wins, ties broken by server
+    /// preference (the order of [`ContentType::supported`]). When the header is
+    /// absent, empty or matches nothing we fall back to `default` — the runtime
+    /// passes the request's own content type, which is itself JSON by default.
+    pub fn negotiate(accept: Option<&str>, default: ContentType) -> ContentType {
+        let accept = match accept {
+            Some(value) if !value.trim().is_empty() => value,
+            _ => return default,
+        };
+
+        let ranges: Vec<(String, f32)> = accept
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let media = parts.next()?.trim().to_ascii_lowercase();
+                if media.is_empty() {
+                    return None;
+                }
+                Some((media, quality_of(parts)))
+            })
+            .collect();
+
+        let mut best: Option<(f32, ContentType)> = None;
+        for ct in ContentType::supported() {
+            // Take the quality of the most specific range matching any of this
+            // type's MIME aliases; an exact match beats `type/*` beats `*/*`.
+            let mut chosen: Option<(u8, f32)> = None;
+            for (media, q) in &ranges {
+                for alias in ct.mime_aliases() {
+                    if let Some(specificity) = match_specificity(media, alias) {
+                        let better = chosen.map_or(true, |(best_spec, best_q)| {
+                            specificity > best_spec || (specificity == best_spec && *q > best_q)
+                        });
+                        if better {
+                            chosen = Some((specificity, *q));
+                        }
+                    }
+                }
+            }
+            let quality = chosen.map_or(f32::NEG_INFINITY, |(_, q)| q);
+            if quality <= 0.0 {
+                continue;
+            }
+            if best.as_ref().map_or(true, |(best_q, _)| quality > *best_q) {
+                best = Some((quality, ct));
+            }
+        }
+
+        best.map(|(_, ct)| ct).unwrap_or(default)
+    }
+
+    /// Select the response content type for an invocation, applying the FDK's
+    /// default fallback chain: honour the caller's `Accept` header, otherwise
+    /// echo the request's own content type, otherwise JSON.
+    ///
+    /// This is the entry point the runtime calls per invocation before encoding
+    /// the returned value; it keeps the "request content type, then JSON"
+    /// default in one place rather than on each caller.
+    pub fn negotiate_response(accept: Option<&str>, request: Option<ContentType>) -> ContentType {
+        Self::negotiate(accept, request.unwrap_or(ContentType::JSON))
+    }
+
+    /// Encode `value` using the `try_encode_*` method matching this type.
+    pub fn encode<T: OutputCoercible>(&self, value: T) -> Result<Vec<u8>, FunctionError> {
+        match self {
+            ContentType::JSON => value.try_encode_json(),
+            ContentType::YAML => value.try_encode_yaml(),
+            ContentType::XML => value.try_encode_xml(),
+            ContentType::TOML => value.try_encode_toml(),
+            ContentType::RON => value.try_encode_ron(),
+            ContentType::Plain => value.try_encode_plain(),
+            ContentType::URLEncoded => value.try_encode_urlencoded(),
+        }
+    }
+}
+
+/// Split a media type into its `(type, subtype)`, defaulting the subtype to `*`.
+fn split_media_type(value: &str) -> (&str, &str) {
+    match value.split_once('/') {
+        Some((main, sub)) => (main, sub),
+        None => (value, "*"),
+    }
+}
+
+/// How specifically an `Accept` media range matches a concrete media type.
+///
+/// Returns `None` when the range does not match at all, otherwise the
+/// specificity used to rank competing ranges: `2` for an exact `type/subtype`,
+/// `1` for a `type/*` wildcard, `0` for `*/*`.
+fn match_specificity(range: &str, supported: &str) -> Option<u8> {
+    if range == "*/*" || range == "*" {
+        return Some(0);
+    }
+    let (range_type, range_sub) = split_media_type(range);
+    let (sup_type, sup_sub) = split_media_type(supported);
+    if !range_type.eq_ignore_ascii_case(sup_type) {
+        return None;
+    }
+    if range_sub == "*" {
+        Some(1)
+    } else if range_sub.eq_ignore_ascii_case(sup_sub) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Read the `q=` quality weight from a media range's parameters (default `1.0`).
+fn quality_of<'a>(params: impl Iterator<Item = &'a str>) -> f32 {
+    for param in params {
+        if let Some(value) = param.trim().strip_prefix("q=") {
+            return value.trim().parse().unwrap_or(0.0);
+        }
+    }
+    1.0
 }
 
 /// An `InputCoercible` type can be generated from a `Vec<u8>`.
 pub trait InputCoercible: Sized {
-    fn try_decode_plain(input: Vec<u8>) -> Result<Self, FunctionError>;
-    fn try_decode_json(input: Vec<u8>) -> Result<Self, FunctionError>;
-    fn try_decode_xml(input: Vec<u8>) -> Result<Self, FunctionError>;
-    fn try_decode_yaml(input: Vec<u8>) -> Result<Self, FunctionError>;
-    fn try_decode_urlencoded(input: Vec<u8>) -> Result<Self, FunctionError>;
+    fn try_decode_plain(input: &[u8]) -> Result<Self, FunctionError>;
+    fn try_decode_json(input: &[u8]) -> Result<Self, FunctionError>;
+    fn try_decode_xml(input: &[u8]) -> Result<Self, FunctionError>;
+    fn try_decode_yaml(input: &[u8]) -> Result<Self, FunctionError>;
+    fn try_decode_urlencoded(input: &[u8]) -> Result<Self, FunctionError>;
+    fn try_decode_toml(input: &[u8]) -> Result<Self, FunctionError>;
+    fn try_decode_ron(input: &[u8]) -> Result<Self, FunctionError>;
+
+    /// Try every supported format until one deserializes, starting with `hint`.
+    ///
+    /// Clients frequently send the wrong `Content-Type` or none at all; rather
+    /// than trusting the header blindly (and defaulting unknown types to JSON)
+    /// this sniffs the body by attempting the hinted format first and then JSON,
+    /// YAML, XML, TOML, RON, URL-encoded and plain in turn, returning the first
+    /// success. If
+    /// nothing decodes the error aggregates every format tried and why it failed.
+    fn try_decode_any(input: &[u8], hint: Option<ContentType>) -> Result<Self, FunctionError> {
+        let mut order = vec![
+            ContentType::JSON,
+            ContentType::YAML,
+            ContentType::XML,
+            ContentType::TOML,
+            ContentType::RON,
+            ContentType::URLEncoded,
+            ContentType::Plain,
+        ];
+        if let Some(hint) = hint {
+            order.retain(|ct| ct.as_header_value() != hint.as_header_value());
+            order.insert(0, hint);
+        }
+
+        let mut attempts = Vec::with_capacity(order.len());
+        for ct in order {
+            let decoded = match ct {
+                ContentType::JSON => Self::try_decode_json(input),
+                ContentType::YAML => Self::try_decode_yaml(input),
+                ContentType::XML => Self::try_decode_xml(input),
+                ContentType::TOML => Self::try_decode_toml(input),
+                ContentType::RON => Self::try_decode_ron(input),
+                ContentType::URLEncoded => Self::try_decode_urlencoded(input),
+                ContentType::Plain => Self::try_decode_plain(input),
+            };
+            match decoded {
+                Ok(value) => return Ok(value),
+                Err(e) => attempts.push(format!("{} ({:?})", ct.as_header_value(), e)),
+            }
+        }
+
+        Err(FunctionError::Coercion {
+            content_type: None,
+            source: format!("no supported format could decode the input; tried {}", attempts.join(", ")).into(),
+        })
+    }
 }
 
 /// An `OutputCoercible` type can be converted to a `Vec<u8>`.
@@ -56,52 +264,93 @@ pub trait OutputCoercible: Sized {
     fn try_encode_json(self) -> Result<Vec<u8>, FunctionError>;
     fn try_encode_xml(self) -> Result<Vec<u8>, FunctionError>;
     fn try_encode_yaml(self) -> Result<Vec<u8>, FunctionError>;
+    fn try_encode_toml(self) -> Result<Vec<u8>, FunctionError>;
+    fn try_encode_ron(self) -> Result<Vec<u8>, FunctionError>;
     fn try_encode_plain(self) -> Result<Vec<u8>, FunctionError>;
     fn try_encode_urlencoded(self) -> Result<Vec<u8>, FunctionError>;
 }
 
+/// Interpret a request body as UTF-8 text, surfacing invalid bytes as a
+/// `Coercion` error tagged with the `content_type` that was being decoded.
+fn decode_utf8(input: &[u8], content_type: ContentType) -> Result<&str, FunctionError> {
+    std::str::from_utf8(input).map_err(|e| FunctionError::Coercion {
+        content_type: Some(content_type),
+        source: Box::new(e),
+    })
+}
+
 impl<T: for<'de> Deserialize<'de>> InputCoercible for T {
-    fn try_decode_plain(input: Vec<u8>) -> Result<Self, FunctionError> {
-        match serde_plain::from_str(&input.iter().map(|&v| v as char).collect::<String>()) {
+    fn try_decode_plain(input: &[u8]) -> Result<Self, FunctionError> {
+        let text = decode_utf8(input, ContentType::Plain)?;
+        match serde_plain::from_str(text) {
             Ok(t) => Ok(t),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::Plain),
+                source: Box::new(e),
             }),
         }
     }
 
-    fn try_decode_json(input: Vec<u8>) -> Result<Self, FunctionError> {
-        match serde_json::from_slice(input.as_slice()) {
+    fn try_decode_json(input: &[u8]) -> Result<Self, FunctionError> {
+        match serde_json::from_slice(input) {
             Ok(t) => Ok(t),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::JSON),
+                source: Box::new(e),
             }),
         }
     }
 
-    fn try_decode_xml(input: Vec<u8>) -> Result<Self, FunctionError> {
-        match serde_xml_rs::from_str(&input.iter().map(|&v| v as char).collect::<String>()) {
+    fn try_decode_xml(input: &[u8]) -> Result<Self, FunctionError> {
+        let text = decode_utf8(input, ContentType::XML)?;
+        match serde_xml_rs::from_str(text) {
             Ok(t) => Ok(t),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::XML),
+                source: Box::new(e),
             }),
         }
     }
 
-    fn try_decode_yaml(input: Vec<u8>) -> Result<Self, FunctionError> {
-        match serde_yaml::from_slice(input.as_slice()) {
+    fn try_decode_yaml(input: &[u8]) -> Result<Self, FunctionError> {
+        match serde_yaml::from_slice(input) {
             Ok(t) => Ok(t),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::YAML),
+                source: Box::new(e),
             }),
         }
     }
 
-    fn try_decode_urlencoded(input: Vec<u8>) -> Result<Self, FunctionError> {
-        match serde_urlencoded::from_str(&input.iter().map(|&v| v as char).collect::<String>()) {
+    fn try_decode_urlencoded(input: &[u8]) -> Result<Self, FunctionError> {
+        let text = decode_utf8(input, ContentType::URLEncoded)?;
+        match serde_urlencoded::from_str(text) {
             Ok(t) => Ok(t),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::URLEncoded),
+                source: Box::new(e),
+            }),
+        }
+    }
+
+    fn try_decode_toml(input: &[u8]) -> Result<Self, FunctionError> {
+        let text = decode_utf8(input, ContentType::TOML)?;
+        match toml::from_str(text) {
+            Ok(t) => Ok(t),
+            Err(e) => Err(FunctionError::Coercion {
+                content_type: Some(ContentType::TOML),
+                source: Box::new(e),
+            }),
+        }
+    }
+
+    fn try_decode_ron(input: &[u8]) -> Result<Self, FunctionError> {
+        let text = decode_utf8(input, ContentType::RON)?;
+        match ron::de::from_str(text) {
+            Ok(t) => Ok(t),
+            Err(e) => Err(FunctionError::Coercion {
+                content_type: Some(ContentType::RON),
+                source: Box::new(e),
             }),
         }
     }
@@ -112,15 +361,17 @@ impl<T: Serialize> OutputCoercible for T {
         match serde_json::to_vec(&self) {
             Ok(vector) => Ok(vector),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::JSON),
+                source: Box::new(e),
             }),
         }
     }
     fn try_encode_xml(self) -> Result<Vec<u8>, FunctionError> {
         match serde_xml_rs::to_string(&self) {
-            Ok(vector) => Ok(vector.chars().map(|ch| ch as u8).collect()),
+            Ok(vector) => Ok(vector.into_bytes()),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::XML),
+                source: Box::new(e),
             }),
         }
     }
@@ -128,39 +379,219 @@ impl<T: Serialize> OutputCoercible for T {
         match serde_yaml::to_vec(&self) {
             Ok(vector) => Ok(vector),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::YAML),
+                source: Box::new(e),
+            }),
+        }
+    }
+
+    fn try_encode_toml(self) -> Result<Vec<u8>, FunctionError> {
+        match toml::to_string(&self) {
+            Ok(vector) => Ok(vector.into_bytes()),
+            Err(e) => Err(FunctionError::Coercion {
+                content_type: Some(ContentType::TOML),
+                source: Box::new(e),
+            }),
+        }
+    }
+
+    fn try_encode_ron(self) -> Result<Vec<u8>, FunctionError> {
+        match ron::ser::to_string(&self) {
+            Ok(vector) => Ok(vector.into_bytes()),
+            Err(e) => Err(FunctionError::Coercion {
+                content_type: Some(ContentType::RON),
+                source: Box::new(e),
             }),
         }
     }
 
     fn try_encode_plain(self) -> Result<Vec<u8>, FunctionError> {
         match serde_plain::to_string(&self) {
-            Ok(vector) => Ok(vector.chars().map(|ch| ch as u8).collect()),
+            Ok(vector) => Ok(vector.into_bytes()),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::Plain),
+                source: Box::new(e),
             }),
         }
     }
 
     fn try_encode_urlencoded(self) -> Result<Vec<u8>, FunctionError> {
         match serde_urlencoded::to_string(&self) {
-            Ok(vector) => Ok(vector.chars().map(|ch| ch as u8).collect()),
+            Ok(vector) => Ok(vector.into_bytes()),
             Err(e) => Err(FunctionError::Coercion {
-                inner: e.to_string(),
+                content_type: Some(ContentType::URLEncoded),
+                source: Box::new(e),
             }),
         }
     }
 }
 
+/// A raw HTTP request envelope exposing the parts the typed-body coercions hide.
+///
+/// Typed `InputCoercible` bodies only ever see the request payload; a function
+/// that needs the request method, the headers, the original `Content-Type` or
+/// the query string takes an `HttpRequest` instead. Because the `try_decode_*`
+/// dispatch is handed the body alone, the runtime builds the envelope directly
+/// with [`HttpRequest::from_parts`] rather than through that dispatch; the
+/// `InputCoercible` impl is a body-only fallback that leaves the envelope
+/// empty and never parses the payload.
+#[derive(Clone, Debug, Default)]
+pub struct HttpRequest {
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+    pub query: String,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Build an envelope from the invocation's real request parts.
+    ///
+    /// This is the path the runtime uses to populate `method`, `headers` and
+    /// `query` alongside the raw `body`; the `try_decode_*` dispatch only ever
+    /// receives the body and so cannot reconstruct them.
+    pub fn from_parts(
+        method: String,
+        headers: Vec<(String, String)>,
+        query: String,
+        body: Vec<u8>,
+    ) -> Self {
+        HttpRequest {
+            method,
+            headers,
+            query,
+            body,
+        }
+    }
+
+    /// Return the first value of `name`, matched case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The declared request content type, if any.
+    pub fn content_type(&self) -> Option<ContentType> {
+        self.header("content-type").map(ContentType::from_str)
+    }
+}
+
+impl InputCoercible for HttpRequest {
+    fn try_decode_plain(input: &[u8]) -> Result<Self, FunctionError> {
+        Ok(HttpRequest {
+            body: input.to_vec(),
+            ..HttpRequest::default()
+        })
+    }
+    fn try_decode_json(input: &[u8]) -> Result<Self, FunctionError> {
+        Self::try_decode_plain(input)
+    }
+    fn try_decode_xml(input: &[u8]) -> Result<Self, FunctionError> {
+        Self::try_decode_plain(input)
+    }
+    fn try_decode_yaml(input: &[u8]) -> Result<Self, FunctionError> {
+        Self::try_decode_plain(input)
+    }
+    fn try_decode_urlencoded(input: &[u8]) -> Result<Self, FunctionError> {
+        Self::try_decode_plain(input)
+    }
+    fn try_decode_toml(input: &[u8]) -> Result<Self, FunctionError> {
+        Self::try_decode_plain(input)
+    }
+    fn try_decode_ron(input: &[u8]) -> Result<Self, FunctionError> {
+        Self::try_decode_plain(input)
+    }
+}
+
+/// A raw HTTP response envelope letting a function set the status and headers.
+///
+/// Returning an `HttpResponse` bypasses body-only encoding: the runtime writes
+/// `status`, the supplied `headers` and the raw `body` straight onto the wire,
+/// so a function can control far more than the single returned value a typed
+/// `OutputCoercible` body allows.
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Default for HttpResponse {
+    fn default() -> Self {
+        HttpResponse {
+            status: 200,
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+}
+
+impl HttpResponse {
+    /// An empty `200 OK` response carrying `body`.
+    pub fn new(body: Vec<u8>) -> Self {
+        HttpResponse {
+            body,
+            ..HttpResponse::default()
+        }
+    }
+
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+impl OutputCoercible for HttpResponse {
+    fn try_encode_json(self) -> Result<Vec<u8>, FunctionError> {
+        Ok(self.body)
+    }
+    fn try_encode_xml(self) -> Result<Vec<u8>, FunctionError> {
+        Ok(self.body)
+    }
+    fn try_encode_yaml(self) -> Result<Vec<u8>, FunctionError> {
+        Ok(self.body)
+    }
+    fn try_encode_toml(self) -> Result<Vec<u8>, FunctionError> {
+        Ok(self.body)
+    }
+    fn try_encode_ron(self) -> Result<Vec<u8>, FunctionError> {
+        Ok(self.body)
+    }
+    fn try_encode_plain(self) -> Result<Vec<u8>, FunctionError> {
+        Ok(self.body)
+    }
+    fn try_encode_urlencoded(self) -> Result<Vec<u8>, FunctionError> {
+        Ok(self.body)
+    }
+}
+
+// `()` is already `InputCoercible` through the blanket `Deserialize` impl above,
+// but only because it deserializes from the unit value: a function taking `()`
+// accepts an empty or unit body (e.g. JSON `null`) and errors with `Coercion`
+// on anything else, so it is not a blanket "ignore the body" escape hatch. A
+// function that needs to accept or inspect an arbitrary body without decoding
+// it takes `HttpRequest` instead.
+
 #[cfg(test)]
 mod tests {
 
 use super::*;
 
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Greeting {
+        name: String,
+    }
+
     // Parsing empty input should result in empty string.
     #[test]
     fn empty_text_plain() {
-        let res = String::try_decode_plain(vec![]);
+        let res = String::try_decode_plain(b"");
         println!("{:?}", res);
         match res {
             Ok(str) => assert_eq!("", str),
@@ -168,6 +599,140 @@ use super::*;
         }
     }
 
+    // The hinted format is tried first and wins even when another format
+    // would also accept the body: `"ada"` is a JSON string, while as plain
+    // text it keeps its quotes.
+    #[test]
+    fn decode_any_prefers_hint() {
+        let body = br#""ada""#;
+        let hinted = String::try_decode_any(body, Some(ContentType::Plain)).unwrap();
+        assert_eq!(hinted, r#""ada""#);
+        let sniffed = String::try_decode_any(body, None).unwrap();
+        assert_eq!(sniffed, "ada");
+    }
+
+    // With no hint the JSON deserializer still picks up a JSON body.
+    #[test]
+    fn decode_any_sniffs_without_hint() {
+        let json = br#"{"name":"grace"}"#;
+        let decoded: Greeting = Greeting::try_decode_any(json, None).unwrap();
+        assert_eq!(decoded.name, "grace");
+    }
+
+    // When nothing decodes the error names every format that was attempted.
+    #[test]
+    fn decode_any_aggregates_failures() {
+        let err = Greeting::try_decode_any(b"\x00\x01\x02", None).unwrap_err();
+        let message = err.to_string();
+        for mime in ["application/json", "application/toml", "text/plain"] {
+            assert!(message.contains(mime), "missing {mime} in {message}");
+        }
+    }
+
+    // Non-ASCII UTF-8 survives a plain decode/encode round-trip; the old
+    // `byte as char` path corrupted anything outside ASCII.
+    #[test]
+    fn plain_round_trip_is_utf8_clean() {
+        let text = "héllo — 世界";
+        let decoded = String::try_decode_plain(text.as_bytes()).unwrap();
+        assert_eq!(text, decoded);
+        let encoded = decoded.try_encode_plain().unwrap();
+        assert_eq!(text.as_bytes(), encoded.as_slice());
+    }
+
+    // Invalid UTF-8 surfaces as a Coercion error rather than silent mojibake.
+    #[test]
+    fn plain_rejects_invalid_utf8() {
+        assert!(String::try_decode_plain(&[0xff, 0xfe]).is_err());
+    }
+
+    // TOML bodies round-trip through the encode/decode pair.
+    #[test]
+    fn toml_round_trip() {
+        let greeting = Greeting { name: "ada".into() };
+        let bytes = Greeting { name: "ada".into() }.try_encode_toml().unwrap();
+        let decoded = Greeting::try_decode_toml(&bytes).unwrap();
+        assert_eq!(greeting, decoded);
+    }
+
+    // RON bodies round-trip through the encode/decode pair.
+    #[test]
+    fn ron_round_trip() {
+        let greeting = Greeting { name: "grace".into() };
+        let bytes = Greeting { name: "grace".into() }.try_encode_ron().unwrap();
+        let decoded = Greeting::try_decode_ron(&bytes).unwrap();
+        assert_eq!(greeting, decoded);
+    }
+
+    // Highest `q=` weight wins regardless of listing order.
+    #[test]
+    fn negotiate_honours_quality() {
+        let chosen = ContentType::negotiate(
+            Some("application/json;q=0.3, text/yaml;q=0.9"),
+            ContentType::JSON,
+        );
+        assert_eq!(chosen.as_header_value(), YAML_TEXT_MIME);
+    }
+
+    // `*/*` falls back to the server's top preference (JSON).
+    #[test]
+    fn negotiate_wildcard_prefers_server_order() {
+        let chosen = ContentType::negotiate(Some("*/*"), ContentType::XML);
+        assert_eq!(chosen.as_header_value(), JSON_MIME);
+    }
+
+    // `q=0` rejects a type, so the next acceptable one wins.
+    #[test]
+    fn negotiate_rejects_zero_quality() {
+        let chosen = ContentType::negotiate(
+            Some("application/json;q=0, text/yaml"),
+            ContentType::XML,
+        );
+        assert_eq!(chosen.as_header_value(), YAML_TEXT_MIME);
+    }
+
+    // A broad `*/*` must not override a narrower per-type weight: JSON keeps
+    // its explicit q=0.3 while YAML rides the `*/*` at q=1 and so wins.
+    #[test]
+    fn negotiate_specific_beats_wildcard() {
+        let chosen = ContentType::negotiate(
+            Some("application/json;q=0.3, */*"),
+            ContentType::XML,
+        );
+        assert_eq!(chosen.as_header_value(), YAML_TEXT_MIME);
+    }
+
+    // The `*/*, type;q=0` exclusion idiom really excludes the type even though
+    // `*/*` would otherwise re-raise it.
+    #[test]
+    fn negotiate_wildcard_with_zero_exclusion() {
+        let chosen = ContentType::negotiate(
+            Some("*/*, application/json;q=0"),
+            ContentType::XML,
+        );
+        assert_eq!(chosen.as_header_value(), YAML_TEXT_MIME);
+    }
+
+    // A non-canonical but accepted MIME spelling still matches its type.
+    #[test]
+    fn negotiate_matches_mime_alias() {
+        let chosen = ContentType::negotiate(Some("application/yaml"), ContentType::JSON);
+        assert_eq!(chosen.as_header_value(), YAML_TEXT_MIME);
+    }
+
+    // Absent Accept echoes the request's own content type, then JSON.
+    #[test]
+    fn negotiate_response_defaults() {
+        assert_eq!(
+            ContentType::negotiate_response(None, Some(ContentType::YAML)).as_header_value(),
+            YAML_TEXT_MIME,
+        );
+        assert_eq!(
+            ContentType::negotiate_response(None, None).as_header_value(),
+            JSON_MIME,
+        );
+    }
+
     // #[test]
     // fn empty_text_json() {
     //     let res = Option::<String>::try_decode_json(vec![]);